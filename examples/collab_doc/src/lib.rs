@@ -0,0 +1,76 @@
+use crate::types::{Change, Document};
+use ic_cdk::{post_upgrade, query, update};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+mod state;
+mod types;
+
+/// Appends `bytes` as a change from `actor_id` at `seq`, then rebuilds the
+/// materialized document from the full change log in `(actor_id, seq)`
+/// order. Deduped by content hash and folded in that deterministic order
+/// (rather than call-arrival order) on every apply, so two canisters that
+/// receive the same changes in different orders converge to the same
+/// document without waiting for a `post_upgrade` replay.
+#[update]
+fn apply_change(actor_id: u64, seq: u64, bytes: Vec<u8>) {
+    let hash = hash_bytes(&bytes);
+    if state::SEEN_HASHES.with_borrow(|seen| seen.contains_key(&hash)) {
+        return;
+    }
+
+    let change = Change {
+        actor_id,
+        seq,
+        hash,
+        bytes,
+    };
+    state::CHANGES.with_borrow_mut(|changes| changes.insert((actor_id, seq), change));
+    state::SEEN_HASHES.with_borrow_mut(|seen| seen.insert(hash, ()));
+
+    rebuild_document();
+}
+
+#[query]
+fn get_document() -> Vec<u8> {
+    state::DOCUMENT.with_borrow(|doc| doc.get().0.clone())
+}
+
+#[query]
+fn get_changes_since(seq: u64) -> Vec<Change> {
+    state::CHANGES.with_borrow(|changes| {
+        changes
+            .iter()
+            .filter(|((_, change_seq), _)| *change_seq >= seq)
+            .map(|(_, change)| change)
+            .collect()
+    })
+}
+
+#[post_upgrade]
+fn post_upgrade() {
+    rebuild_document();
+}
+
+/// Folds every stored change into the materialized document in the
+/// `StableBTreeMap`'s natural `(actor_id, seq)` key order. Used on every
+/// `apply_change` as well as `post_upgrade`, so convergence never depends on
+/// an upgrade having happened.
+fn rebuild_document() {
+    let materialized = state::CHANGES.with_borrow(|changes| {
+        changes
+            .iter()
+            .fold(Vec::new(), |mut materialized, (_, change)| {
+                materialized.extend_from_slice(&change.bytes);
+                materialized
+            })
+    });
+
+    state::DOCUMENT.with_borrow_mut(|doc| doc.set(Document(materialized)).unwrap());
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,42 @@
+use candid::{CandidType, Deserialize};
+use ic_stable_structures::{storable::Bound, Storable};
+use std::borrow::Cow;
+
+/// An opaque change in the CRDT change log, keyed by the actor that
+/// authored it plus that actor's monotonically increasing sequence number.
+#[derive(CandidType, Deserialize, Clone)]
+pub struct Change {
+    pub actor_id: u64,
+    pub seq: u64,
+    pub hash: u64,
+    pub bytes: Vec<u8>,
+}
+
+impl Storable for Change {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).expect("failed to encode change"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("failed to decode change")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// The materialized document, folded from applied changes in deterministic
+/// order.
+#[derive(Clone, Default)]
+pub struct Document(pub Vec<u8>);
+
+impl Storable for Document {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Document(bytes.into_owned())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -0,0 +1,316 @@
+use crate::types::{
+    AskKey, BidKey, Fill, Order, OrderLocation, Side, TransferFromArgs, TransferFromError,
+};
+use candid::{Nat, Principal};
+use ic_cdk::{call::Call, query, update};
+use icrc_ledger_types::icrc1::account::Account;
+use std::cell::RefCell;
+
+mod state;
+mod types;
+
+const LEDGER_CANISTER_ID: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+
+thread_local! {
+    static BUSY: RefCell<bool> = const { RefCell::new(false) };
+}
+
+/// Rejects reentrant calls into `place_order`/`cancel_order` while another
+/// `place_order` is awaiting ledger settlement. Without this, a resting
+/// order read before an `await` could be matched twice (double-fill) or
+/// cancelled out from under an in-flight fill before this call resumes and
+/// mutates it.
+struct ReentrancyGuard;
+
+impl ReentrancyGuard {
+    fn acquire() -> Self {
+        BUSY.with_borrow_mut(|busy| {
+            if *busy {
+                ic_cdk::trap("dex canister is busy processing another order");
+            }
+            *busy = true;
+        });
+        ReentrancyGuard
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    fn drop(&mut self) {
+        BUSY.with_borrow_mut(|busy| *busy = false);
+    }
+}
+
+/// Places an order and matches it against the opposite book while prices
+/// cross, at the resting order's price (price-time priority). Any
+/// unmatched remainder rests on this order's own side of the book.
+///
+/// Each matched leg's book state is mutated *before* awaiting settlement, so
+/// a concurrent call (blocked by `ReentrancyGuard` in practice, but this
+/// also holds if that guard is ever relaxed) never observes a stale,
+/// already-matched resting order. If settlement for a leg fails, that leg's
+/// mutation is compensated (the resting order is restored) and matching
+/// stops there; fills already settled and recorded earlier in the same call
+/// are kept, since their tokens genuinely moved.
+#[update]
+async fn place_order(side: Side, price: u64, amount: u64) -> Vec<Fill> {
+    let _guard = ReentrancyGuard::acquire();
+
+    let owner = ic_cdk::api::msg_caller();
+    let order_id = next_order_id();
+    let timestamp = ic_cdk::api::time();
+    let mut remaining = amount;
+    let mut fills = Vec::new();
+
+    match side {
+        Side::Bid => {
+            while remaining > 0 {
+                let best_ask = best_ask();
+                let (ask_key, mut ask_order) = match best_ask {
+                    Some((key, order)) if order.price <= price => (key, order),
+                    _ => break,
+                };
+
+                let fill_amount = remaining.min(ask_order.amount);
+                let original_amount = ask_order.amount;
+                ask_order.amount -= fill_amount;
+                apply_ask_fill(ask_key, ask_order.clone());
+
+                if settle(ask_order.owner, owner, fill_amount).await.is_err() {
+                    ask_order.amount = original_amount;
+                    apply_ask_fill(ask_key, ask_order);
+                    break;
+                }
+
+                remaining -= fill_amount;
+                fills.push(record_fill(
+                    order_id,
+                    ask_order.id,
+                    ask_order.price,
+                    fill_amount,
+                    timestamp,
+                ));
+            }
+
+            if remaining > 0 {
+                insert_bid(order_id, owner, price, remaining, timestamp);
+            }
+        }
+        Side::Ask => {
+            while remaining > 0 {
+                let best_bid = best_bid();
+                let (bid_key, mut bid_order) = match best_bid {
+                    Some((key, order)) if order.price >= price => (key, order),
+                    _ => break,
+                };
+
+                let fill_amount = remaining.min(bid_order.amount);
+                let original_amount = bid_order.amount;
+                bid_order.amount -= fill_amount;
+                apply_bid_fill(bid_key, bid_order.clone());
+
+                if settle(owner, bid_order.owner, fill_amount).await.is_err() {
+                    bid_order.amount = original_amount;
+                    apply_bid_fill(bid_key, bid_order);
+                    break;
+                }
+
+                remaining -= fill_amount;
+                fills.push(record_fill(
+                    bid_order.id,
+                    order_id,
+                    bid_order.price,
+                    fill_amount,
+                    timestamp,
+                ));
+            }
+
+            if remaining > 0 {
+                insert_ask(order_id, owner, price, remaining, timestamp);
+            }
+        }
+    }
+
+    fills
+}
+
+#[update]
+fn cancel_order(order_id: u64) {
+    let _guard = ReentrancyGuard::acquire();
+
+    let Some(location) =
+        state::ORDER_LOCATIONS.with_borrow(|locations| locations.get(&order_id))
+    else {
+        return;
+    };
+
+    match location.side {
+        Side::Bid => {
+            let key = BidKey::new(location.price, location.timestamp, order_id);
+            state::BIDS.with_borrow_mut(|bids| bids.remove(&key));
+        }
+        Side::Ask => {
+            let key = AskKey::new(location.price, location.timestamp, order_id);
+            state::ASKS.with_borrow_mut(|asks| asks.remove(&key));
+        }
+    }
+    state::ORDER_LOCATIONS.with_borrow_mut(|locations| locations.remove(&order_id));
+}
+
+#[query]
+fn get_order_book() -> (Vec<Order>, Vec<Order>) {
+    let bids = state::BIDS.with_borrow(|bids| bids.iter().map(|(_, order)| order).collect());
+    let asks = state::ASKS.with_borrow(|asks| asks.iter().map(|(_, order)| order).collect());
+    (bids, asks)
+}
+
+#[query]
+fn get_recent_fills(limit: u64) -> Vec<Fill> {
+    state::FILLS.with_borrow(|fills| {
+        fills
+            .iter()
+            .rev()
+            .take(limit as usize)
+            .map(|(_, fill)| fill)
+            .collect()
+    })
+}
+
+fn best_ask() -> Option<(AskKey, Order)> {
+    state::ASKS.with_borrow(|asks| asks.iter().next())
+}
+
+fn best_bid() -> Option<(BidKey, Order)> {
+    state::BIDS.with_borrow(|bids| bids.iter().next())
+}
+
+fn apply_ask_fill(key: AskKey, order: Order) {
+    state::ASKS.with_borrow_mut(|asks| {
+        if order.amount == 0 {
+            asks.remove(&key);
+            state::ORDER_LOCATIONS.with_borrow_mut(|locations| locations.remove(&order.id));
+        } else {
+            asks.insert(key, order);
+        }
+    });
+}
+
+fn apply_bid_fill(key: BidKey, order: Order) {
+    state::BIDS.with_borrow_mut(|bids| {
+        if order.amount == 0 {
+            bids.remove(&key);
+            state::ORDER_LOCATIONS.with_borrow_mut(|locations| locations.remove(&order.id));
+        } else {
+            bids.insert(key, order);
+        }
+    });
+}
+
+fn insert_bid(order_id: u64, owner: Principal, price: u64, amount: u64, timestamp: u64) {
+    let key = BidKey::new(price, timestamp, order_id);
+    let order = Order {
+        id: order_id,
+        owner,
+        price,
+        amount,
+        timestamp,
+    };
+    state::BIDS.with_borrow_mut(|bids| bids.insert(key, order));
+    state::ORDER_LOCATIONS.with_borrow_mut(|locations| {
+        locations.insert(
+            order_id,
+            OrderLocation {
+                side: Side::Bid,
+                price,
+                timestamp,
+            },
+        )
+    });
+}
+
+fn insert_ask(order_id: u64, owner: Principal, price: u64, amount: u64, timestamp: u64) {
+    let key = AskKey::new(price, timestamp, order_id);
+    let order = Order {
+        id: order_id,
+        owner,
+        price,
+        amount,
+        timestamp,
+    };
+    state::ASKS.with_borrow_mut(|asks| asks.insert(key, order));
+    state::ORDER_LOCATIONS.with_borrow_mut(|locations| {
+        locations.insert(
+            order_id,
+            OrderLocation {
+                side: Side::Ask,
+                price,
+                timestamp,
+            },
+        )
+    });
+}
+
+fn record_fill(
+    bid_order_id: u64,
+    ask_order_id: u64,
+    price: u64,
+    amount: u64,
+    timestamp: u64,
+) -> Fill {
+    let fill = Fill {
+        bid_order_id,
+        ask_order_id,
+        price,
+        amount,
+        timestamp,
+    };
+    let seq = state::NEXT_FILL_SEQ.with_borrow_mut(|next_seq| {
+        let seq = *next_seq.get();
+        next_seq.set(seq + 1).unwrap();
+        seq
+    });
+    state::FILLS.with_borrow_mut(|fills| fills.insert(seq, fill.clone()));
+
+    fill
+}
+
+fn next_order_id() -> u64 {
+    state::NEXT_ORDER_ID.with_borrow_mut(|next_id| {
+        let id = *next_id.get();
+        next_id.set(id + 1).unwrap();
+        id
+    })
+}
+
+/// Settles a trade leg by pulling `amount` from `from`'s ICRC-1 ledger
+/// fixture balance to `to`, via an ICRC-2 allowance pre-approved in favor of
+/// this canister. Returns the ledger's error (e.g. the owner never approved
+/// the DEX, or has insufficient funds) rather than trapping, so the caller
+/// can compensate the book mutation it already made for this leg.
+async fn settle(from: Principal, to: Principal, amount: u64) -> Result<(), TransferFromError> {
+    let ledger = Principal::from_text(LEDGER_CANISTER_ID).unwrap();
+    let arg = TransferFromArgs {
+        spender_subaccount: None,
+        from: Account {
+            owner: from,
+            subaccount: None,
+        },
+        to: Account {
+            owner: to,
+            subaccount: None,
+        },
+        amount: Nat::from(amount),
+        fee: None,
+        memo: None,
+        created_at_time: None,
+    };
+
+    let (result,): (Result<Nat, TransferFromError>,) =
+        Call::bounded_wait(ledger, "icrc2_transfer_from")
+            .with_arg(arg)
+            .await
+            .expect("settlement call failed")
+            .candid()
+            .expect("failed to decode settlement response");
+
+    result.map(|_| ())
+}
@@ -0,0 +1,165 @@
+use candid::{CandidType, Deserialize, Nat, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use icrc_ledger_types::icrc1::account::Account;
+use std::borrow::Cow;
+
+#[derive(CandidType, Deserialize, Clone, Copy)]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct Order {
+    pub id: u64,
+    pub owner: Principal,
+    pub price: u64,
+    pub amount: u64,
+    pub timestamp: u64,
+}
+
+impl Storable for Order {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).expect("failed to encode order"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("failed to decode order")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Sorts bids descending by price (via the inverted price), ascending by
+/// timestamp so earlier orders at the same price win: price-time priority.
+#[derive(CandidType, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BidKey {
+    pub inverted_price: u64,
+    pub timestamp: u64,
+    pub order_id: u64,
+}
+
+impl BidKey {
+    pub fn new(price: u64, timestamp: u64, order_id: u64) -> Self {
+        BidKey {
+            inverted_price: u64::MAX - price,
+            timestamp,
+            order_id,
+        }
+    }
+}
+
+impl Storable for BidKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).expect("failed to encode bid key"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("failed to decode bid key")
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 40,
+        is_fixed_size: false,
+    };
+}
+
+/// Sorts asks ascending by price then timestamp: price-time priority.
+#[derive(CandidType, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AskKey {
+    pub price: u64,
+    pub timestamp: u64,
+    pub order_id: u64,
+}
+
+impl AskKey {
+    pub fn new(price: u64, timestamp: u64, order_id: u64) -> Self {
+        AskKey {
+            price,
+            timestamp,
+            order_id,
+        }
+    }
+}
+
+impl Storable for AskKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).expect("failed to encode ask key"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("failed to decode ask key")
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 40,
+        is_fixed_size: false,
+    };
+}
+
+#[derive(CandidType, Deserialize, Clone, Copy)]
+pub struct OrderLocation {
+    pub side: Side,
+    pub price: u64,
+    pub timestamp: u64,
+}
+
+impl Storable for OrderLocation {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).expect("failed to encode order location"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("failed to decode order location")
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 32,
+        is_fixed_size: false,
+    };
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct Fill {
+    pub bid_order_id: u64,
+    pub ask_order_id: u64,
+    pub price: u64,
+    pub amount: u64,
+    pub timestamp: u64,
+}
+
+impl Storable for Fill {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).expect("failed to encode fill"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("failed to decode fill")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct TransferFromArgs {
+    pub spender_subaccount: Option<[u8; 32]>,
+    pub from: Account,
+    pub to: Account,
+    pub amount: Nat,
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum TransferFromError {
+    BadFee { expected_fee: Nat },
+    InsufficientFunds { balance: Nat },
+    InsufficientAllowance { allowance: Nat },
+    Expired { ledger_time: u64 },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
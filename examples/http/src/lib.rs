@@ -1,4 +1,4 @@
-use ic_asset_certification::{Asset, AssetConfig, AssetRouter};
+use ic_asset_certification::{Asset, AssetConfig, AssetFallbackConfig, AssetRouter};
 use ic_cdk::{
     api::{certified_data_set, data_certificate},
     *,
@@ -11,18 +11,41 @@ thread_local! {
 }
 
 const INDEX_HTML: &[u8] = include_bytes!("index.html");
+const INDEX_HTML_GZIP: &[u8] = include_bytes!("index.html.gz");
+const INDEX_HTML_BROTLI: &[u8] = include_bytes!("index.html.br");
+const NOT_FOUND_HTML: &[u8] = include_bytes!("404.html");
 
 #[init]
 fn init() {
-    let assets = vec![Asset::new("index.html", INDEX_HTML)];
-    let asset_configs = vec![AssetConfig::File {
-        path: "index.html".to_string(),
-        content_type: Some("text/html".to_string()),
-        headers: vec![],
-        fallback_for: vec![],
-        aliased_by: vec![],
-        encodings: vec![],
-    }];
+    let assets = vec![
+        Asset::new("index.html", INDEX_HTML),
+        Asset::new("index.html.gz", INDEX_HTML_GZIP),
+        Asset::new("index.html.br", INDEX_HTML_BROTLI),
+        Asset::new("404.html", NOT_FOUND_HTML),
+    ];
+    let asset_configs = vec![
+        AssetConfig::File {
+            path: "index.html".to_string(),
+            content_type: Some("text/html".to_string()),
+            headers: vec![],
+            fallback_for: vec![],
+            aliased_by: vec![],
+            encodings: vec![
+                ("gzip".to_string(), "index.html.gz".to_string()),
+                ("br".to_string(), "index.html.br".to_string()),
+            ],
+        },
+        AssetConfig::File {
+            path: "404.html".to_string(),
+            content_type: Some("text/html".to_string()),
+            headers: vec![],
+            fallback_for: vec![AssetFallbackConfig {
+                scope: "/".to_string(),
+            }],
+            aliased_by: vec![],
+            encodings: vec![],
+        },
+    ];
 
     ASSET_ROUTER.with_borrow_mut(|asset_router| {
         if let Err(err) = asset_router.certify_assets(assets, asset_configs) {
@@ -41,13 +64,15 @@ fn post_upgrade() {
 #[query]
 fn http_request(req: HttpRequest) -> HttpResponse {
     ASSET_ROUTER.with_borrow(|asset_router| {
-        if let Ok(response) = asset_router.serve_asset(
-            &data_certificate().expect("No data certificate available"),
-            &req,
-        ) {
-            response
-        } else {
-            ic_cdk::trap("Failed to serve asset");
-        }
+        // `serve_asset` negotiates the best registered encoding against the
+        // request's `Accept-Encoding` header, slices the body and emits
+        // `Content-Range`/`Accept-Ranges` for `Range` requests, and falls
+        // back to the certified 404 asset above instead of trapping on a miss.
+        asset_router
+            .serve_asset(
+                &data_certificate().expect("No data certificate available"),
+                &req,
+            )
+            .unwrap_or_else(|err| ic_cdk::trap(&format!("Failed to serve asset: {}", err)))
     })
 }
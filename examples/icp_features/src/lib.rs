@@ -1,34 +1,253 @@
-use candid::{Nat, Principal};
-use ic_cdk::{
-    call::{Call, CallResult},
-    update,
+use crate::state::{APPROVALS, BALANCES};
+use crate::types::{
+    Allowance, AllowanceArgs, ApproveArgs, ApproveError, MetadataValue, StandardRecord,
+    StorableAccount, StoredAllowance, TransferArg, TransferError, TransferFromArgs,
+    TransferFromError,
 };
+use candid::Nat;
+use ic_cdk::{query, update};
 use icrc_ledger_types::icrc1::account::Account;
 
-const LEDGER_CANISTER_ID: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+mod state;
+mod types;
+
+const FEE: u128 = 10_000;
+const TOKEN_NAME: &str = "Example Token";
+const TOKEN_SYMBOL: &str = "EXT";
+const TOKEN_DECIMALS: u8 = 8;
+
+#[query]
+fn icrc1_balance_of(account: Account) -> Nat {
+    BALANCES.with_borrow(|balances| {
+        Nat::from(balances.get(&StorableAccount(account)).unwrap_or_default())
+    })
+}
+
+#[query]
+fn icrc1_fee() -> Nat {
+    Nat::from(FEE)
+}
+
+#[query]
+fn icrc1_metadata() -> Vec<(String, MetadataValue)> {
+    vec![
+        (
+            "icrc1:name".to_string(),
+            MetadataValue::Text(TOKEN_NAME.to_string()),
+        ),
+        (
+            "icrc1:symbol".to_string(),
+            MetadataValue::Text(TOKEN_SYMBOL.to_string()),
+        ),
+        (
+            "icrc1:decimals".to_string(),
+            MetadataValue::Nat(Nat::from(TOKEN_DECIMALS)),
+        ),
+        ("icrc1:fee".to_string(), MetadataValue::Nat(Nat::from(FEE))),
+    ]
+}
+
+#[query]
+fn icrc1_total_supply() -> Nat {
+    BALANCES.with_borrow(|balances| {
+        balances
+            .iter()
+            .fold(0u128, |supply, (_, balance)| supply + balance)
+    })
+    .into()
+}
+
+#[query]
+fn icrc1_supported_standards() -> Vec<StandardRecord> {
+    vec![
+        StandardRecord {
+            name: "ICRC-1".to_string(),
+            url: "https://github.com/dfinity/ICRC-1".to_string(),
+        },
+        StandardRecord {
+            name: "ICRC-2".to_string(),
+            url: "https://github.com/dfinity/ICRC-1/tree/main/standards/ICRC-2".to_string(),
+        },
+    ]
+}
+
+#[update]
+fn icrc1_transfer(arg: TransferArg) -> Result<Nat, TransferError> {
+    let from = Account {
+        owner: ic_cdk::api::msg_caller(),
+        subaccount: arg.from_subaccount,
+    };
+    let fee = arg.fee.clone().map(nat_to_u128).unwrap_or(FEE);
+    if fee != FEE {
+        return Err(TransferError::BadFee {
+            expected_fee: Nat::from(FEE),
+        });
+    }
+
+    let amount = nat_to_u128(arg.amount.clone());
+    let total = amount + fee;
+
+    let from_key = StorableAccount(from);
+    let from_balance = BALANCES.with_borrow(|balances| balances.get(&from_key).unwrap_or_default());
+    if from_balance < total {
+        return Err(TransferError::InsufficientFunds {
+            balance: Nat::from(from_balance),
+        });
+    }
+
+    BALANCES.with_borrow_mut(|balances| {
+        balances.insert(from_key, from_balance - total);
+        let to_key = StorableAccount(arg.to);
+        let to_balance = balances.get(&to_key).unwrap_or_default();
+        balances.insert(to_key, to_balance + amount);
+    });
+
+    Ok(arg.amount)
+}
 
 #[update]
-async fn get_balance(owner: Principal) -> Nat {
-    let ledger_principal = Principal::from_text(LEDGER_CANISTER_ID).unwrap();
-    let ledger = LedgerService(ledger_principal);
-    let (balance,) = ledger
-        .icrc_1_balance_of(Account {
-            owner,
-            subaccount: None,
-        })
-        .await
-        .unwrap();
-
-    balance
-}
-
-struct LedgerService(Principal);
-
-impl LedgerService {
-    async fn icrc_1_balance_of(&self, arg0: Account) -> CallResult<(Nat,)> {
-        Ok(Call::bounded_wait(self.0, "icrc1_balance_of")
-            .with_arg(arg0)
-            .await?
-            .candid()?)
+fn icrc2_approve(arg: ApproveArgs) -> Result<Nat, ApproveError> {
+    let from = Account {
+        owner: ic_cdk::api::msg_caller(),
+        subaccount: arg.from_subaccount,
+    };
+    let fee = arg.fee.clone().map(nat_to_u128).unwrap_or(FEE);
+    if fee != FEE {
+        return Err(ApproveError::BadFee {
+            expected_fee: Nat::from(FEE),
+        });
+    }
+
+    let from_key = StorableAccount(from);
+    let balance = BALANCES.with_borrow(|balances| balances.get(&from_key).unwrap_or_default());
+    if balance < fee {
+        return Err(ApproveError::InsufficientFunds {
+            balance: Nat::from(balance),
+        });
     }
+    BALANCES.with_borrow_mut(|balances| balances.insert(from_key, balance - fee));
+
+    let allowance = nat_to_u128(arg.amount.clone());
+    APPROVALS.with_borrow_mut(|approvals| {
+        approvals.insert(
+            (from_key, StorableAccount(arg.spender)),
+            StoredAllowance {
+                allowance,
+                expires_at: arg.expires_at,
+            },
+        );
+    });
+
+    Ok(arg.amount)
+}
+
+#[query]
+fn icrc2_allowance(arg: AllowanceArgs) -> Allowance {
+    APPROVALS.with_borrow(|approvals| {
+        match approvals.get(&(StorableAccount(arg.account), StorableAccount(arg.spender))) {
+            Some(stored) if !is_expired(stored.expires_at) => Allowance {
+                allowance: Nat::from(stored.allowance),
+                expires_at: stored.expires_at,
+            },
+            _ => Allowance {
+                allowance: Nat::from(0u32),
+                expires_at: None,
+            },
+        }
+    })
+}
+
+#[update]
+fn icrc2_transfer_from(arg: TransferFromArgs) -> Result<Nat, TransferFromError> {
+    let spender = Account {
+        owner: ic_cdk::api::msg_caller(),
+        subaccount: arg.spender_subaccount,
+    };
+    let fee = arg.fee.clone().map(nat_to_u128).unwrap_or(FEE);
+    if fee != FEE {
+        return Err(TransferFromError::BadFee {
+            expected_fee: Nat::from(FEE),
+        });
+    }
+    let amount = nat_to_u128(arg.amount.clone());
+    let total = amount + fee;
+
+    let allowance_key = (StorableAccount(arg.from), StorableAccount(spender));
+    let stored = match APPROVALS.with_borrow(|approvals| approvals.get(&allowance_key)) {
+        Some(stored) if is_expired(stored.expires_at) => {
+            return Err(TransferFromError::Expired {
+                ledger_time: ic_cdk::api::time(),
+            });
+        }
+        Some(stored) => stored,
+        None => {
+            return Err(TransferFromError::InsufficientAllowance {
+                allowance: Nat::from(0u32),
+            });
+        }
+    };
+    if stored.allowance < total {
+        return Err(TransferFromError::InsufficientAllowance {
+            allowance: Nat::from(stored.allowance),
+        });
+    }
+
+    let from_key = StorableAccount(arg.from);
+    let from_balance = BALANCES.with_borrow(|balances| balances.get(&from_key).unwrap_or_default());
+    if from_balance < total {
+        return Err(TransferFromError::InsufficientFunds {
+            balance: Nat::from(from_balance),
+        });
+    }
+
+    BALANCES.with_borrow_mut(|balances| {
+        balances.insert(from_key, from_balance - total);
+        let to_key = StorableAccount(arg.to);
+        let to_balance = balances.get(&to_key).unwrap_or_default();
+        balances.insert(to_key, to_balance + amount);
+    });
+
+    APPROVALS.with_borrow_mut(|approvals| {
+        approvals.insert(
+            allowance_key,
+            StoredAllowance {
+                allowance: stored.allowance - total,
+                expires_at: stored.expires_at,
+            },
+        );
+    });
+
+    Ok(arg.amount)
+}
+
+/// Test-only: seeds `account`'s balance directly so pic-js tests can set up
+/// ledger state without minting it through `icrc1_transfer`.
+#[update]
+fn set_balance(account: Account, amount: Nat) {
+    BALANCES.with_borrow_mut(|balances| {
+        balances.insert(StorableAccount(account), nat_to_u128(amount));
+    });
+}
+
+/// Test-only: seeds an ICRC-2 allowance directly so pic-js tests can set up
+/// approvals without calling `icrc2_approve`.
+#[update]
+fn set_allowance(from: Account, spender: Account, amount: Nat, expires_at: Option<u64>) {
+    APPROVALS.with_borrow_mut(|approvals| {
+        approvals.insert(
+            (StorableAccount(from), StorableAccount(spender)),
+            StoredAllowance {
+                allowance: nat_to_u128(amount),
+                expires_at,
+            },
+        );
+    });
+}
+
+fn is_expired(expires_at: Option<u64>) -> bool {
+    expires_at.is_some_and(|expiry| expiry <= ic_cdk::api::time())
+}
+
+fn nat_to_u128(nat: Nat) -> u128 {
+    nat.0.to_string().parse().expect("amount does not fit in u128")
 }
@@ -0,0 +1,139 @@
+use candid::{CandidType, Deserialize, Nat};
+use ic_stable_structures::{storable::Bound, Storable};
+use icrc_ledger_types::icrc1::account::Account;
+use std::borrow::Cow;
+
+/// Wraps `Account` so it can be used as a `StableBTreeMap` key; `Account`
+/// itself doesn't implement `Storable`.
+#[derive(CandidType, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StorableAccount(pub Account);
+
+impl Storable for StorableAccount {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self.0).expect("failed to encode account"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        StorableAccount(candid::decode_one(&bytes).expect("failed to decode account"))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 64,
+        is_fixed_size: false,
+    };
+}
+
+#[derive(CandidType, Deserialize, Clone, Copy)]
+pub struct StoredAllowance {
+    pub allowance: u128,
+    pub expires_at: Option<u64>,
+}
+
+impl Storable for StoredAllowance {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).expect("failed to encode allowance"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("failed to decode allowance")
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 32,
+        is_fixed_size: false,
+    };
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct TransferArg {
+    pub from_subaccount: Option<[u8; 32]>,
+    pub to: Account,
+    pub amount: Nat,
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum TransferError {
+    BadFee { expected_fee: Nat },
+    InsufficientFunds { balance: Nat },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct ApproveArgs {
+    pub from_subaccount: Option<[u8; 32]>,
+    pub spender: Account,
+    pub amount: Nat,
+    pub expected_allowance: Option<Nat>,
+    pub expires_at: Option<u64>,
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ApproveError {
+    BadFee { expected_fee: Nat },
+    InsufficientFunds { balance: Nat },
+    AllowanceChanged { current_allowance: Nat },
+    Expired { ledger_time: u64 },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct AllowanceArgs {
+    pub account: Account,
+    pub spender: Account,
+}
+
+#[derive(CandidType)]
+pub struct Allowance {
+    pub allowance: Nat,
+    pub expires_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct TransferFromArgs {
+    pub spender_subaccount: Option<[u8; 32]>,
+    pub from: Account,
+    pub to: Account,
+    pub amount: Nat,
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum TransferFromError {
+    BadFee { expected_fee: Nat },
+    InsufficientFunds { balance: Nat },
+    InsufficientAllowance { allowance: Nat },
+    Expired { ledger_time: u64 },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
+#[derive(CandidType)]
+pub struct StandardRecord {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(CandidType)]
+pub enum MetadataValue {
+    Nat(Nat),
+    Text(String),
+}
@@ -1,15 +1,18 @@
+use crate::types::{
+    MakeProposalAction, NeuronId, Proposal, Vote, STATUS_ADOPTED, STATUS_OPEN, STATUS_REJECTED,
+};
 use candid::CandidType;
 use ic_cdk::*;
 
+mod state;
+mod types;
+
 #[allow(dead_code, unused_imports)]
 mod governance {
     include!(concat!(env!("OUT_DIR"), "/governance.rs"));
 }
 
-#[derive(CandidType)]
-struct NeuronId {
-    id: u64,
-}
+const VOTING_PERIOD_NANOS: u64 = 4 * 24 * 60 * 60 * 1_000_000_000;
 
 #[derive(CandidType)]
 struct ProposalInfo {
@@ -17,6 +20,9 @@ struct ProposalInfo {
     status: i32,
     title: Option<String>,
     summary: Option<String>,
+    yes_votes: u64,
+    no_votes: u64,
+    deadline: u64,
 }
 
 #[update]
@@ -25,8 +31,8 @@ async fn get_pending_proposals() -> Vec<ProposalInfo> {
         before_proposal: None,
         exclude_topic: vec![],
         include_reward_status: vec![0, 1, 2, 3, 4, 5],
-        include_status: vec![1],
         include_all_manage_neuron_proposals: None,
+        include_status: vec![1],
         omit_large_fields: None,
         limit: 100,
     })
@@ -41,6 +47,158 @@ async fn get_pending_proposals() -> Vec<ProposalInfo> {
             status: proposal.status,
             title: proposal.proposal.as_ref().and_then(|s| s.title.clone()),
             summary: proposal.proposal.as_ref().map(|s| s.summary.clone()),
+            yes_votes: 0,
+            no_votes: 0,
+            deadline: 0,
         })
         .collect()
 }
+
+/// Returns a single locally-tracked proposal, with its current vote tally
+/// and status, so tests can observe the outcome of `manage_neuron` /
+/// `register_vote` / `fast_forward_proposal_deadline` above.
+#[query]
+fn get_proposal(proposal_id: u64) -> Option<ProposalInfo> {
+    state::PROPOSALS
+        .with_borrow(|proposals| proposals.get(&proposal_id).map(|p| to_proposal_info(&p)))
+}
+
+/// Returns every locally-tracked proposal, with its current vote tally and
+/// status.
+#[query]
+fn list_local_proposals() -> Vec<ProposalInfo> {
+    state::PROPOSALS.with_borrow(|proposals| {
+        proposals
+            .iter()
+            .map(|(_, proposal)| to_proposal_info(&proposal))
+            .collect()
+    })
+}
+
+fn to_proposal_info(proposal: &Proposal) -> ProposalInfo {
+    ProposalInfo {
+        id: Some(NeuronId { id: proposal.id }),
+        status: proposal.status,
+        title: Some(proposal.title.clone()),
+        summary: Some(proposal.summary.clone()),
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        deadline: proposal.deadline,
+    }
+}
+
+/// Test-only: registers `voting_power` for `neuron_id` so pic-js tests can
+/// set up neurons with a known weight before exercising the proposal
+/// lifecycle below.
+#[update]
+fn set_neuron_voting_power(neuron_id: NeuronId, voting_power: u64) {
+    state::NEURON_VOTING_POWER.with_borrow_mut(|neurons| {
+        neurons.insert(neuron_id.id, voting_power);
+    });
+}
+
+/// Test-only: sets the voting-power threshold that `tally` adopts/rejects
+/// proposals against, so pic-js tests can exercise outcomes without relying
+/// on the hardcoded default.
+#[update]
+fn set_voting_threshold(threshold: u64) {
+    state::VOTING_THRESHOLD.with_borrow_mut(|voting_threshold| {
+        voting_threshold.set(threshold).unwrap();
+    });
+}
+
+/// Submits a proposal on behalf of `neuron_id`, mirroring `manage_neuron`'s
+/// `MakeProposal` command, and returns the new proposal's id.
+#[update]
+fn manage_neuron(neuron_id: NeuronId, action: MakeProposalAction) -> u64 {
+    let _ = neuron_id;
+
+    let id = state::NEXT_PROPOSAL_ID.with_borrow_mut(|next_id| {
+        let id = *next_id.get();
+        next_id.set(id + 1).unwrap();
+        id
+    });
+
+    let proposal = Proposal {
+        id,
+        title: action.title,
+        summary: action.summary,
+        status: STATUS_OPEN,
+        yes_votes: 0,
+        no_votes: 0,
+        deadline: ic_cdk::api::time() + VOTING_PERIOD_NANOS,
+    };
+    state::PROPOSALS.with_borrow_mut(|proposals| proposals.insert(id, proposal));
+
+    id
+}
+
+/// Casts `vote` from `neuron_id` on `proposal_id`, mirroring `register_vote`,
+/// and tallies the cumulative voting power against the configured threshold.
+#[update]
+fn register_vote(neuron_id: NeuronId, proposal_id: u64, vote: Vote) {
+    let already_voted =
+        state::VOTES.with_borrow(|votes| votes.contains_key(&(proposal_id, neuron_id.id)));
+    if already_voted {
+        return;
+    }
+
+    let mut proposal = match state::PROPOSALS.with_borrow(|proposals| proposals.get(&proposal_id)) {
+        Some(proposal) if proposal.status == STATUS_OPEN => proposal,
+        _ => return,
+    };
+
+    let voting_power =
+        state::NEURON_VOTING_POWER.with_borrow(|neurons| neurons.get(&neuron_id.id).unwrap_or(0));
+    match vote {
+        Vote::Yes => proposal.yes_votes += voting_power,
+        Vote::No => proposal.no_votes += voting_power,
+    }
+    state::VOTES.with_borrow_mut(|votes| votes.insert((proposal_id, neuron_id.id), ()));
+
+    tally(&mut proposal);
+    state::PROPOSALS.with_borrow_mut(|proposals| proposals.insert(proposal_id, proposal));
+}
+
+/// Test-only: moves `proposal_id`'s deadline into the past so timed
+/// rejection can be reproduced deterministically under PocketIC's
+/// controllable time.
+#[update]
+fn fast_forward_proposal_deadline(proposal_id: u64) {
+    state::PROPOSALS.with_borrow_mut(|proposals| {
+        if let Some(mut proposal) = proposals.get(&proposal_id) {
+            proposal.deadline = ic_cdk::api::time();
+            proposals.insert(proposal_id, proposal);
+        }
+    });
+}
+
+#[heartbeat]
+fn heartbeat() {
+    let now = ic_cdk::api::time();
+    let expired: Vec<u64> = state::PROPOSALS.with_borrow(|proposals| {
+        proposals
+            .iter()
+            .filter(|(_, proposal)| proposal.status == STATUS_OPEN && proposal.deadline <= now)
+            .map(|(id, _)| id)
+            .collect()
+    });
+
+    for id in expired {
+        state::PROPOSALS.with_borrow_mut(|proposals| {
+            if let Some(mut proposal) = proposals.get(&id) {
+                proposal.status = STATUS_REJECTED;
+                proposals.insert(id, proposal);
+            }
+        });
+    }
+}
+
+fn tally(proposal: &mut Proposal) {
+    let threshold = state::VOTING_THRESHOLD.with_borrow(|threshold| *threshold.get());
+    if proposal.yes_votes >= threshold {
+        proposal.status = STATUS_ADOPTED;
+    } else if proposal.no_votes >= threshold {
+        proposal.status = STATUS_REJECTED;
+    }
+}
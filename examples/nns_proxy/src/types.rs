@@ -0,0 +1,47 @@
+use candid::{CandidType, Deserialize};
+use ic_stable_structures::{storable::Bound, Storable};
+use std::borrow::Cow;
+
+pub const STATUS_OPEN: i32 = 1;
+pub const STATUS_REJECTED: i32 = 2;
+pub const STATUS_ADOPTED: i32 = 3;
+
+#[derive(CandidType, Deserialize, Clone, Copy)]
+pub struct NeuronId {
+    pub id: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct MakeProposalAction {
+    pub title: String,
+    pub summary: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Copy)]
+pub enum Vote {
+    Yes,
+    No,
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct Proposal {
+    pub id: u64,
+    pub title: String,
+    pub summary: String,
+    pub status: i32,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub deadline: u64,
+}
+
+impl Storable for Proposal {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).expect("failed to encode proposal"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).expect("failed to decode proposal")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
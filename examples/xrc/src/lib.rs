@@ -0,0 +1,103 @@
+use ic_cdk::{
+    init, post_upgrade, query, update,
+    management_canister::{
+        http_request, HttpHeader, HttpMethod, HttpRequestArgs, HttpRequestResult, TransformArgs,
+        TransformContext,
+    },
+};
+use std::time::Duration;
+
+mod state;
+mod types;
+
+use types::CachedRate;
+
+const SOURCE_URL: &str = "https://api.example.com/v1/xdr-rate";
+const REFRESH_INTERVAL_SECS: u64 = 60 * 60;
+
+#[init]
+fn init() {
+    ic_cdk_timers::set_timer_interval(Duration::from_secs(REFRESH_INTERVAL_SECS), || {
+        ic_cdk::futures::spawn(async {
+            refresh_rate().await;
+        });
+    });
+}
+
+#[post_upgrade]
+fn post_upgrade() {
+    init();
+}
+
+/// Fetches the current fiat/XDR rate via an HTTP outcall, using a transform
+/// that strips non-deterministic headers and normalizes the body to a
+/// fixed-decimals rate so every replica agrees on the response.
+#[update]
+async fn refresh_rate() {
+    let request = HttpRequestArgs {
+        url: SOURCE_URL.to_string(),
+        method: HttpMethod::GET,
+        body: None,
+        max_response_bytes: Some(4_096),
+        headers: vec![HttpHeader {
+            name: "Accept".to_string(),
+            value: "application/json".to_string(),
+        }],
+        transform: Some(TransformContext::from_name(
+            "transform_rate_response".to_string(),
+            vec![],
+        )),
+        is_replicated: None,
+    };
+
+    // `http_request` returns the body the IC already ran through
+    // `transform_rate_response` for consensus, so this reads the
+    // transform's normalized `"rate"` field rather than re-scraping the
+    // raw upstream body.
+    let response = http_request(&request).await.expect("http outcall failed");
+    let rate_permyriad = parse_rate_permyriad(&response.body);
+
+    state::CACHED_RATE.with_borrow_mut(|cell| {
+        cell.set(CachedRate {
+            rate_permyriad,
+            fetched_at: ic_cdk::api::time(),
+        })
+        .unwrap();
+    });
+}
+
+#[query]
+fn get_xdr_rate() -> CachedRate {
+    state::CACHED_RATE.with_borrow(|cell| *cell.get())
+}
+
+#[query]
+fn transform_rate_response(args: TransformArgs) -> HttpRequestResult {
+    let rate_permyriad = parse_rate_permyriad(&args.response.body);
+
+    HttpRequestResult {
+        status: args.response.status,
+        headers: vec![],
+        // Keep emitting a `"rate"` field (now fixed-decimals) rather than
+        // renaming it, so `refresh_rate` can parse the transformed body
+        // with the same `parse_rate_permyriad` helper used on the raw body.
+        body: format!("{{\"rate\":{:.4}}}", rate_permyriad as f64 / 10_000.0).into_bytes(),
+    }
+}
+
+/// Scrapes a `"rate": <float>` field out of the response body and converts
+/// it to permyriad (rate * 10_000, rounded), giving a deterministic
+/// fixed-point value regardless of how many decimals the upstream API sends.
+fn parse_rate_permyriad(body: &[u8]) -> u64 {
+    let text = String::from_utf8_lossy(body);
+    let rate_str = text
+        .split("\"rate\"")
+        .nth(1)
+        .and_then(|rest| rest.split_once(':'))
+        .map(|(_, rest)| rest.trim_start())
+        .and_then(|rest| rest.split(|c: char| c == ',' || c == '}').next())
+        .expect("response did not contain a rate field");
+
+    let rate: f64 = rate_str.trim().parse().expect("rate was not a number");
+    (rate * 10_000.0).round() as u64
+}
@@ -0,0 +1,30 @@
+use candid::CandidType;
+use ic_stable_structures::{storable::Bound, Storable};
+use std::borrow::Cow;
+
+#[derive(CandidType, Clone, Copy, Default)]
+pub struct CachedRate {
+    pub rate_permyriad: u64,
+    pub fetched_at: u64,
+}
+
+impl Storable for CachedRate {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        let mut bytes = Vec::with_capacity(16);
+        bytes.extend_from_slice(&self.rate_permyriad.to_le_bytes());
+        bytes.extend_from_slice(&self.fetched_at.to_le_bytes());
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        CachedRate {
+            rate_permyriad: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            fetched_at: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+        }
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 16,
+        is_fixed_size: true,
+    };
+}